@@ -0,0 +1,75 @@
+//! Build configuration for the glyph-buffer compiler.
+//!
+//! Parsed from an optional TOML file passed as the second CLI argument. When no file is given
+//! the defaults reproduce the historical behaviour: Roboto-Regular, face 0, the whole codepoint
+//! space.
+
+use serde::Deserialize;
+
+/// A single inclusive codepoint range to rasterize.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Range {
+    fn full() -> Self {
+        Self {
+            start: char::MIN as u32,
+            end: char::MAX as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    /// Path to the font file to load at runtime.
+    #[serde(default = "default_font")]
+    pub font: std::path::PathBuf,
+    /// Face index, for font collections.
+    #[serde(default)]
+    pub face: u32,
+    /// Codepoint ranges to rasterize; empty means the whole codepoint space.
+    #[serde(default)]
+    pub ranges: Vec<Range>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            font: default_font(),
+            face: 0,
+            ranges: Vec::new(),
+        }
+    }
+}
+
+fn default_font() -> std::path::PathBuf {
+    std::path::PathBuf::from("Roboto-Regular.ttf")
+}
+
+impl Config {
+    /// Load the config from `path`, falling back to defaults when no path is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error string if the file cannot be read or does not parse as TOML.
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| format!("reading {path}: {e}"))?;
+        toml::from_str(&text).map_err(|e| format!("parsing {path}: {e}"))
+    }
+
+    /// Iterate over every codepoint selected by the configured ranges.
+    pub fn codepoints(&self) -> impl Iterator<Item = u32> + '_ {
+        let ranges = if self.ranges.is_empty() {
+            vec![Range::full()]
+        } else {
+            self.ranges.clone()
+        };
+        ranges.into_iter().flat_map(|r| r.start..=r.end)
+    }
+}
@@ -0,0 +1,107 @@
+//! Self-describing binary container for the compiled glyph buffers.
+//!
+//! A single file replaces the raw memory dumps: a fixed [`Header`] (magic, version, endianness
+//! marker, and a `(count, offset)` pair per section) is followed by the glyph curves, the page
+//! directory, the packed info and metrics pages, and the sorted kerning table. A loader can validate the magic and version
+//! before trusting any offset, and the section counts/offsets make element sizes and alignment
+//! explicit.
+
+use crate::kerning::KernPair;
+use crate::{Curve, GlyphInfo, Metrics};
+
+/// Magic identifier at the start of every container.
+pub const MAGIC: [u8; 4] = *b"BGTM";
+/// Current container format version.
+pub const VERSION: u32 = 4;
+/// Endianness marker, written in native byte order so a reader can detect a mismatch.
+pub const ENDIANNESS: u32 = 0x0102_0304;
+/// Alignment every section is padded to, matching the 16-byte alignment of [`Curve`]/[`GlyphInfo`].
+pub const SECTION_ALIGN: usize = 16;
+
+/// Number of entries in a single codepoint page (the low 8 bits of a codepoint).
+pub const PAGE_SIZE: usize = 256;
+/// Number of entries in the page directory: one per high part (`cp >> 8`) of a codepoint.
+pub const DIRECTORY_LEN: usize = (char::MAX as usize >> 8) + 1;
+/// Directory sentinel for a high part with no present glyphs.
+pub const ABSENT_PAGE: u32 = u32::MAX;
+
+/// Fixed-size container header. All integers are stored in the writer's native endianness; the
+/// [`ENDIANNESS`] marker lets a reader verify it matches.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct Header {
+    pub magic: [u8; 4],
+    pub version: u32,
+    pub endianness: u32,
+    pub glyph_count: u32,
+    pub glyph_offset: u32,
+    pub directory_count: u32,
+    pub directory_offset: u32,
+    pub info_count: u32,
+    pub info_offset: u32,
+    pub metrics_count: u32,
+    pub metrics_offset: u32,
+    pub kerning_count: u32,
+    pub kerning_offset: u32,
+}
+
+/// Reinterpret a slice of `Copy` POD elements as raw bytes.
+fn as_bytes<T>(buffer: &[T]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(buffer.as_ptr().cast(), size_of_val(buffer)) }
+}
+
+fn align_up(offset: usize) -> usize {
+    (offset + SECTION_ALIGN - 1) & !(SECTION_ALIGN - 1)
+}
+
+/// Serialize the buffers into a single container at `path`.
+///
+/// `directory` maps each high part to a page index or [`ABSENT_PAGE`]; `infos` and `metrics` are
+/// the packed pages, [`PAGE_SIZE`] entries each.
+///
+/// # Errors
+///
+/// Propagates any I/O error from creating or writing the file.
+pub fn write<P: AsRef<std::path::Path>>(
+    path: P,
+    glyphs: &[Curve],
+    directory: &[u32],
+    infos: &[GlyphInfo],
+    metrics: &[Metrics],
+    kerning: &[KernPair],
+) -> std::io::Result<()> {
+    let glyph_offset = align_up(size_of::<Header>());
+    let directory_offset = align_up(glyph_offset + size_of_val(glyphs));
+    let info_offset = align_up(directory_offset + size_of_val(directory));
+    let metrics_offset = align_up(info_offset + size_of_val(infos));
+    let kerning_offset = align_up(metrics_offset + size_of_val(metrics));
+    let total = kerning_offset + size_of_val(kerning);
+
+    let header = Header {
+        magic: MAGIC,
+        version: VERSION,
+        endianness: ENDIANNESS,
+        glyph_count: u32::try_from(glyphs.len()).unwrap(),
+        glyph_offset: u32::try_from(glyph_offset).unwrap(),
+        directory_count: u32::try_from(directory.len()).unwrap(),
+        directory_offset: u32::try_from(directory_offset).unwrap(),
+        info_count: u32::try_from(infos.len()).unwrap(),
+        info_offset: u32::try_from(info_offset).unwrap(),
+        metrics_count: u32::try_from(metrics.len()).unwrap(),
+        metrics_offset: u32::try_from(metrics_offset).unwrap(),
+        kerning_count: u32::try_from(kerning.len()).unwrap(),
+        kerning_offset: u32::try_from(kerning_offset).unwrap(),
+    };
+
+    let mut bytes = vec![0u8; total];
+    bytes[..size_of::<Header>()].copy_from_slice(as_bytes(std::slice::from_ref(&header)));
+    bytes[glyph_offset..glyph_offset + size_of_val(glyphs)].copy_from_slice(as_bytes(glyphs));
+    bytes[directory_offset..directory_offset + size_of_val(directory)]
+        .copy_from_slice(as_bytes(directory));
+    bytes[info_offset..info_offset + size_of_val(infos)].copy_from_slice(as_bytes(infos));
+    bytes[metrics_offset..metrics_offset + size_of_val(metrics)].copy_from_slice(as_bytes(metrics));
+    bytes[kerning_offset..kerning_offset + size_of_val(kerning)]
+        .copy_from_slice(as_bytes(kerning));
+
+    std::fs::write(path, bytes)
+}
@@ -0,0 +1,115 @@
+//! Kerning extraction from the legacy `kern` table and GPOS pair adjustments.
+//!
+//! The result is a compact table of `(left_glyph, right_glyph)` pairs sorted lexicographically so
+//! the consumer can binary-search it. Adjustments are horizontal advance deltas, normalized by the
+//! same `global_bounding_box` width factor used for the rest of the metrics.
+
+use ttf_parser::gpos::{PairAdjustment, PositioningSubtable};
+use ttf_parser::{Face, GlyphId};
+
+/// A single kerning adjustment between an ordered glyph pair.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct KernPair {
+    pub left: u16,
+    pub right: u16,
+    pub adjustment: f32,
+}
+
+/// Collect horizontal kerning for `glyphs`, normalized by `norm`.
+///
+/// GPOS pair adjustments take precedence over the legacy `kern` table when both are present.
+///
+/// The GPOS branch skips left glyphs outside each subtable's coverage and probes every glyph in
+/// `glyphs` as the right-hand partner of each covered left glyph. The legacy `kern` branch is a
+/// full `O(glyphs²)` probe: `ttf_parser` exposes only the `Subtable::glyphs_kerning` point query
+/// with no way to enumerate the stored pairs, so there is nothing to iterate. Both branches are
+/// bounded by the `glyphs` the caller passes, which `compile` restricts to the configured
+/// codepoint ranges.
+#[must_use]
+pub fn extract(face: &Face, glyphs: &[GlyphId], norm: f32) -> Vec<KernPair> {
+    let mut pairs = std::collections::BTreeMap::new();
+
+    if let Some(kern) = face.tables().kern {
+        for subtable in kern.subtables {
+            if !subtable.horizontal || subtable.variable {
+                continue;
+            }
+            for &left in glyphs {
+                for &right in glyphs {
+                    if let Some(value) = subtable.glyphs_kerning(left, right) {
+                        if value != 0 {
+                            pairs.insert((left.0, right.0), value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(gpos) = face.tables().gpos {
+        for lookup in gpos.lookups {
+            for subtable in lookup.subtables.into_iter::<PositioningSubtable>() {
+                if let PositioningSubtable::Pair(adjustment) = subtable {
+                    extract_gpos_pair(&adjustment, glyphs, &mut pairs);
+                }
+            }
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|((left, right), value)| KernPair {
+            left,
+            right,
+            adjustment: f32::from(value) / norm,
+        })
+        .collect()
+}
+
+/// Pull the horizontal advance adjustment out of a GPOS pair-adjustment subtable. Left glyphs
+/// outside the coverage are skipped; for each covered left glyph, every glyph in `glyphs` is
+/// looked up as the right-hand partner in its pair set (Format 1) or the class matrix (Format 2).
+fn extract_gpos_pair(
+    adjustment: &PairAdjustment,
+    glyphs: &[GlyphId],
+    pairs: &mut std::collections::BTreeMap<(u16, u16), i16>,
+) {
+    match adjustment {
+        PairAdjustment::Format1 { coverage, sets } => {
+            for &left in glyphs {
+                let Some(set) = coverage.get(left).and_then(|index| sets.get(index)) else {
+                    continue;
+                };
+                for &right in glyphs {
+                    if let Some(record) = set.get(right) {
+                        let dx = record.0.x_advance;
+                        if dx != 0 {
+                            pairs.insert((left.0, right.0), dx);
+                        }
+                    }
+                }
+            }
+        }
+        PairAdjustment::Format2 {
+            coverage,
+            classes,
+            matrix,
+        } => {
+            for &left in glyphs {
+                if coverage.get(left).is_none() {
+                    continue;
+                }
+                let left_class = classes.0.get(left);
+                for &right in glyphs {
+                    if let Some(record) = matrix.get(left_class, classes.1.get(right)) {
+                        let dx = record.0.x_advance;
+                        if dx != 0 {
+                            pairs.insert((left.0, right.0), dx);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
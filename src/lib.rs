@@ -0,0 +1,376 @@
+#![warn(clippy::pedantic)]
+
+use ttf_parser::OutlineBuilder;
+
+pub mod config;
+pub mod container;
+pub mod kerning;
+pub mod reader;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C, align(16))]
+pub struct Curve {
+    p0: glam::Vec2,
+    p1: glam::Vec2,
+    p2: glam::Vec2,
+    flags: u32,
+}
+
+impl Curve {
+    #[must_use]
+    pub fn with_p0(self, p0: glam::Vec2) -> Self {
+        Self { p0, ..self }
+    }
+    #[must_use]
+    pub fn with_p1(self, p1: glam::Vec2) -> Self {
+        Self { p1, ..self }
+    }
+    #[must_use]
+    pub fn with_p2(self, p2: glam::Vec2) -> Self {
+        Self { p2, ..self }
+    }
+    #[must_use]
+    pub fn set_line_flag(self) -> Self {
+        Self {
+            flags: self.flags | 1,
+            ..self
+        }
+    }
+    #[must_use]
+    pub fn zeroed() -> Self {
+        Self {
+            p0: glam::Vec2::ZERO,
+            p1: glam::Vec2::ZERO,
+            p2: glam::Vec2::ZERO,
+            flags: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Outline {
+    curves: Vec<Curve>,
+    /// Flatness tolerance for cubic-to-quadratic conversion, in the font's unscaled units.
+    tolerance: f32,
+}
+
+impl Outline {
+    /// Flatten a cubic bezier (current point, `c1`, `c2`, `p3`) into one or more quadratics
+    /// via recursive de Casteljau subdivision, emitting each through the normal `quad_to` path.
+    fn flatten_cubic(&mut self, p0: glam::Vec2, c1: glam::Vec2, c2: glam::Vec2, p3: glam::Vec2) {
+        // Perpendicular distance of the control points from the P0->P3 chord.
+        let chord = p3 - p0;
+        let chord_len = chord.length();
+        let (d1, d2) = if chord_len <= f32::EPSILON {
+            ((c1 - p0).length(), (c2 - p0).length())
+        } else {
+            (
+                (c1 - p0).perp_dot(chord).abs() / chord_len,
+                (c2 - p0).perp_dot(chord).abs() / chord_len,
+            )
+        };
+
+        if d1.max(d2) <= self.tolerance {
+            // Best quadratic approximation shares the cubic's end points.
+            let q = (3.0 * c1 - p0 + 3.0 * c2 - p3) * 0.25;
+            self.quad_to(q.x, q.y, p3.x, p3.y);
+            return;
+        }
+
+        // Split at t = 0.5 and recurse on both halves.
+        let ab = (p0 + c1) * 0.5;
+        let bc = (c1 + c2) * 0.5;
+        let cd = (c2 + p3) * 0.5;
+        let abc = (ab + bc) * 0.5;
+        let bcd = (bc + cd) * 0.5;
+        let mid = (abc + bcd) * 0.5;
+        self.flatten_cubic(p0, ab, abc, mid);
+        self.flatten_cubic(mid, bcd, cd, p3);
+    }
+
+    /// Normalize the outline into its own tight bounding box (0..1) and return that box
+    /// `(min, size)` in font units, so the caller can record the glyph's placement.
+    fn process(&mut self) -> (glam::Vec2, glam::Vec2) {
+        let mut min = glam::Vec2::splat(f32::INFINITY);
+        let mut max = glam::Vec2::splat(f32::NEG_INFINITY);
+        for curve in &self.curves {
+            for p in [curve.p0, curve.p1, curve.p2] {
+                min = min.min(p);
+                max = max.max(p);
+            }
+        }
+        // Guard against a degenerate (zero-extent) axis to avoid dividing by zero.
+        let bbox_min = min;
+        let bbox_size = (max - min).max(glam::Vec2::splat(f32::EPSILON));
+
+        for curve in &mut self.curves {
+            // Normalize curves to range (0, 1)
+            curve.p0 -= bbox_min;
+            curve.p1 -= bbox_min;
+            curve.p2 -= bbox_min;
+            curve.p0 /= bbox_size;
+            curve.p1 /= bbox_size;
+            curve.p2 /= bbox_size;
+
+            // Invert curves and swap first and last point to correct winding order.
+            curve.p0.y = 1.0 - curve.p0.y;
+            curve.p1.y = 1.0 - curve.p1.y;
+            curve.p2.y = 1.0 - curve.p2.y;
+            std::mem::swap(&mut curve.p0, &mut curve.p2);
+        }
+
+        (bbox_min, bbox_size)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.curves.push(Curve::zeroed().with_p0(glam::vec2(x, y)));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let last = self.curves.last_mut().unwrap();
+        *last = last
+            .with_p1(glam::vec2((last.p0[0] + x) * 0.5, (last.p0[1] + y) * 0.5))
+            .with_p2(glam::vec2(x, y))
+            .set_line_flag();
+        self.curves.push(Curve::zeroed().with_p0(glam::vec2(x, y)));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let last = self.curves.last_mut().unwrap();
+        *last = last.with_p1(glam::vec2(x1, y1)).with_p2(glam::vec2(x, y));
+        self.curves.push(Curve::zeroed().with_p0(glam::vec2(x, y)));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p0 = self.curves.last().unwrap().p0;
+        self.flatten_cubic(
+            p0,
+            glam::vec2(x1, y1),
+            glam::vec2(x2, y2),
+            glam::vec2(x, y),
+        );
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn close(&mut self) {
+        assert!(
+            self.curves.last().unwrap().p1 == glam::Vec2::ZERO
+                && self.curves.last().unwrap().p2 == glam::Vec2::ZERO
+        );
+        self.curves.pop();
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Metrics {
+    advance: f32,
+    left_side_bearing: f32,
+    right_side_bearing: f32,
+    vertical_advance: f32,
+    vertical_side_bearing: f32,
+}
+
+impl Metrics {
+    /// Number of `f32` fields, used by the reader to walk the packed metrics section.
+    pub const FIELDS: usize = 5;
+
+    #[must_use]
+    pub fn new(fields: [f32; Self::FIELDS]) -> Self {
+        Self {
+            advance: fields[0],
+            left_side_bearing: fields[1],
+            right_side_bearing: fields[2],
+            vertical_advance: fields[3],
+            vertical_side_bearing: fields[4],
+        }
+    }
+}
+
+///
+/// Outline is in f32 0.0..=1.0, normalized into the glyph's own tight bounding box.
+/// `bbox_min`/`bbox_size` give that box normalized by the global-bbox width, the same space as
+/// `Metrics::advance`, so placement and advance compose; every `metrics` field is in that space too.
+///
+#[derive(Debug)]
+struct GlyphData {
+    outline: Option<Outline>,
+    metrics: Metrics,
+    bbox_min: glam::Vec2,
+    bbox_size: glam::Vec2,
+}
+
+/// Flatness tolerance for cubic-to-quadratic conversion, as a fraction of the font's bbox height.
+const CUBIC_TOLERANCE_FRACTION: f32 = 0.005;
+
+fn get_glyph_data(face: &ttf_parser::Face, c: char) -> Option<GlyphData> {
+    let index = face.glyph_index(c)?;
+    let global_bbox = face.global_bounding_box();
+    let advance = face.glyph_hor_advance(index)?;
+    let mut temp = Outline {
+        tolerance: f32::from(global_bbox.height()) * CUBIC_TOLERANCE_FRACTION,
+        ..Outline::default()
+    };
+    let mut outline = face.outline_glyph(index, &mut temp).map(|_| temp);
+
+    // Everything that positions a glyph — its bbox, advance and side bearings — is normalized by
+    // the same `global_bounding_box` width factor so placements and advances compose in one space.
+    let norm = f32::from(global_bbox.width());
+    let (bbox_min, bbox_size) = outline
+        .as_mut()
+        .map_or((glam::Vec2::ZERO, glam::Vec2::ZERO), |outline| {
+            let (min, size) = outline.process();
+            (min / norm, size / norm)
+        });
+
+    let left_side_bearing = face.glyph_hor_side_bearing(index).unwrap_or(0);
+    let glyph_width = face.glyph_bounding_box(index).map_or(0, |b| b.width());
+    let right_side_bearing = f32::from(advance) - f32::from(left_side_bearing) - f32::from(glyph_width);
+    let metrics = Metrics {
+        advance: f32::from(advance) / norm,
+        left_side_bearing: f32::from(left_side_bearing) / norm,
+        right_side_bearing: right_side_bearing / norm,
+        vertical_advance: f32::from(face.glyph_ver_advance(index).unwrap_or(0)) / norm,
+        vertical_side_bearing: f32::from(face.glyph_ver_side_bearing(index).unwrap_or(0)) / norm,
+    };
+    Some(GlyphData {
+        outline,
+        metrics,
+        bbox_min,
+        bbox_size,
+    })
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct GlyphInfo {
+    start: u32,
+    end: u32,
+    /// Glyph bounding box origin, normalized by the global-bbox width (same space as `advance`).
+    bbox_min: glam::Vec2,
+    /// Glyph bounding box size, normalized by the global-bbox width (same space as `advance`).
+    bbox_size: glam::Vec2,
+}
+
+impl GlyphInfo {
+    #[must_use]
+    pub fn new(start: u32, end: u32, bbox_min: glam::Vec2, bbox_size: glam::Vec2) -> Self {
+        Self {
+            start,
+            end,
+            bbox_min,
+            bbox_size,
+        }
+    }
+    /// Sentinel entry for a codepoint that is absent from a present page.
+    #[must_use]
+    pub fn absent() -> Self {
+        Self {
+            start: u32::MAX,
+            end: u32::MAX,
+            bbox_min: glam::Vec2::ZERO,
+            bbox_size: glam::Vec2::ZERO,
+        }
+    }
+    /// Whether this entry is the [absent](Self::absent) sentinel.
+    #[must_use]
+    pub fn is_absent(&self) -> bool {
+        self.start == u32::MAX
+    }
+    #[must_use]
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+    #[must_use]
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+    #[must_use]
+    pub fn bbox_min(&self) -> glam::Vec2 {
+        self.bbox_min
+    }
+    #[must_use]
+    pub fn bbox_size(&self) -> glam::Vec2 {
+        self.bbox_size
+    }
+}
+
+/// The packed buffers produced by [`compile`], ready to hand to [`container::write`].
+#[derive(Debug, Default)]
+pub struct Compiled {
+    pub glyph_buffer: Vec<Curve>,
+    pub directory: Vec<u32>,
+    pub info_buffer: Vec<GlyphInfo>,
+    pub metrics_buffer: Vec<Metrics>,
+    pub kerning: Vec<kerning::KernPair>,
+}
+
+/// Compile `face` into the packed buffers for the codepoints selected by `config`.
+#[must_use]
+pub fn compile(face: &ttf_parser::Face, config: &config::Config) -> Compiled {
+    let mut map = std::collections::HashMap::new();
+    for u in config.codepoints() {
+        if let Some(c) = char::from_u32(u) {
+            if let Some(data) = get_glyph_data(face, c) {
+                map.insert(c, data);
+            }
+        }
+    }
+
+    // Build a sparse two-level page table: a directory indexed by the high part of a codepoint
+    // (`cp >> 8`) points either at a packed 256-entry page or at `ABSENT_PAGE`. Only pages that
+    // contain at least one present glyph are emitted, so the unassigned majority of the codepoint
+    // space costs one directory slot instead of a full info/metrics entry.
+    let page_size = u32::try_from(container::PAGE_SIZE).unwrap();
+    let mut glyph_buffer = Vec::new();
+    let mut directory = vec![container::ABSENT_PAGE; container::DIRECTORY_LEN];
+    let mut info_buffer = Vec::new();
+    let mut metrics_buffer = Vec::new();
+
+    for hi in 0..u32::try_from(container::DIRECTORY_LEN).unwrap() {
+        let base = hi << 8;
+        let present = (0..page_size)
+            .any(|lo| char::from_u32(base + lo).is_some_and(|c| map.contains_key(&c)));
+        if !present {
+            continue;
+        }
+
+        directory[hi as usize] = u32::try_from(info_buffer.len()).unwrap() / page_size;
+        for lo in 0..page_size {
+            let (info, metrics) = match char::from_u32(base + lo).and_then(|c| map.get(&c)) {
+                Some(data) => {
+                    let (mut start, mut end) = (0, 0);
+                    if let Some(outline) = &data.outline {
+                        start = u32::try_from(glyph_buffer.len()).unwrap();
+                        end = start + u32::try_from(outline.curves.len()).unwrap();
+                        glyph_buffer.extend_from_slice(&outline.curves);
+                    }
+                    let info = GlyphInfo::new(start, end, data.bbox_min, data.bbox_size);
+                    (info, data.metrics)
+                }
+                None => (GlyphInfo::absent(), Metrics::default()),
+            };
+            info_buffer.push(info);
+            metrics_buffer.push(metrics);
+        }
+    }
+
+    let mut glyph_ids = map
+        .keys()
+        .filter_map(|&c| face.glyph_index(c))
+        .collect::<Vec<_>>();
+    glyph_ids.sort_unstable_by_key(|g| g.0);
+    glyph_ids.dedup();
+    let norm = f32::from(face.global_bounding_box().width());
+    let kerning = kerning::extract(face, &glyph_ids, norm);
+
+    Compiled {
+        glyph_buffer,
+        directory,
+        info_buffer,
+        metrics_buffer,
+        kerning,
+    }
+}
@@ -0,0 +1,330 @@
+//! Typed, bounds-checked reader for the [`crate::container`] format.
+//!
+//! [`ReadBinary`] is the low-level binary-utility trait: every accessor validates its range
+//! against the backing slice and returns [`ReadError`] on a short or out-of-range read.
+//! [`Container`] layers the header and section semantics on top, so a loader can verify the
+//! magic and version before trusting any payload.
+
+use crate::kerning::KernPair;
+use crate::{container, Curve, GlyphInfo, Metrics};
+
+/// Error returned by a failed binary read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadError {
+    /// A read of `size` bytes at `offset` ran past the end of the `len`-byte slice.
+    OutOfRange { offset: usize, size: usize, len: usize },
+    /// The magic identifier did not match [`container::MAGIC`].
+    BadMagic,
+    /// The format version is not understood by this reader.
+    BadVersion(u32),
+    /// The endianness marker does not match this platform.
+    Endianness,
+}
+
+/// Low-level native-endian reads over a byte slice, all bounds-checked.
+pub trait ReadBinary {
+    /// The bytes backing this reader.
+    fn bytes(&self) -> &[u8];
+
+    /// Read `N` bytes at `offset`, erroring if they fall outside the slice.
+    fn read_array<const N: usize>(&self, offset: usize) -> Result<[u8; N], ReadError> {
+        let bytes = self.bytes();
+        let end = offset.checked_add(N).ok_or(ReadError::OutOfRange {
+            offset,
+            size: N,
+            len: bytes.len(),
+        })?;
+        let slice = bytes.get(offset..end).ok_or(ReadError::OutOfRange {
+            offset,
+            size: N,
+            len: bytes.len(),
+        })?;
+        Ok(slice.try_into().unwrap())
+    }
+
+    fn read_u32(&self, offset: usize) -> Result<u32, ReadError> {
+        self.read_array::<4>(offset).map(u32::from_ne_bytes)
+    }
+
+    fn read_f32(&self, offset: usize) -> Result<f32, ReadError> {
+        self.read_array::<4>(offset).map(f32::from_ne_bytes)
+    }
+
+    fn read_vec2(&self, offset: usize) -> Result<glam::Vec2, ReadError> {
+        Ok(glam::vec2(self.read_f32(offset)?, self.read_f32(offset + 4)?))
+    }
+
+    /// Read a single [`Curve`] at `offset`, reconstructing it through the public builder API.
+    fn read_curve(&self, offset: usize) -> Result<Curve, ReadError> {
+        let p0 = self.read_vec2(offset)?;
+        let p1 = self.read_vec2(offset + 8)?;
+        let p2 = self.read_vec2(offset + 16)?;
+        let flags = self.read_u32(offset + 24)?;
+        let mut curve = Curve::zeroed().with_p0(p0).with_p1(p1).with_p2(p2);
+        if flags & 1 != 0 {
+            curve = curve.set_line_flag();
+        }
+        Ok(curve)
+    }
+}
+
+impl ReadBinary for [u8] {
+    fn bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Codepoint substituted for a missing alphanumeric glyph.
+pub const REPLACEMENT_CHARACTER: char = '\u{FFFD}';
+
+/// Whether a missing codepoint should fall back to [`REPLACEMENT_CHARACTER`].
+fn should_replace(cp: u32) -> bool {
+    char::from_u32(cp).is_some_and(char::is_alphanumeric)
+}
+
+/// A validated view over a container file.
+#[derive(Debug)]
+pub struct Container<'a> {
+    bytes: &'a [u8],
+    glyph_offset: usize,
+    directory_offset: usize,
+    directory_count: usize,
+    info_offset: usize,
+    metrics_offset: usize,
+    kerning_offset: usize,
+    kerning_count: usize,
+}
+
+impl<'a> Container<'a> {
+    /// Validate the header and return a reader over the payload.
+    ///
+    /// # Errors
+    ///
+    /// Fails on a truncated header, a bad magic/version, or a mismatched endianness marker.
+    pub fn open(bytes: &'a [u8]) -> Result<Self, ReadError> {
+        if bytes.read_array::<4>(0)? != container::MAGIC {
+            return Err(ReadError::BadMagic);
+        }
+        let version = bytes.read_u32(4)?;
+        if version != container::VERSION {
+            return Err(ReadError::BadVersion(version));
+        }
+        if bytes.read_u32(8)? != container::ENDIANNESS {
+            return Err(ReadError::Endianness);
+        }
+        Ok(Self {
+            bytes,
+            glyph_offset: bytes.read_u32(16)? as usize,
+            directory_count: bytes.read_u32(20)? as usize,
+            directory_offset: bytes.read_u32(24)? as usize,
+            info_offset: bytes.read_u32(32)? as usize,
+            metrics_offset: bytes.read_u32(40)? as usize,
+            kerning_count: bytes.read_u32(44)? as usize,
+            kerning_offset: bytes.read_u32(48)? as usize,
+        })
+    }
+
+    /// Read the `index`-th curve of the glyph section.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the curve falls outside the file.
+    pub fn curve(&self, index: u32) -> Result<Curve, ReadError> {
+        let offset = self.glyph_offset + index as usize * size_of::<Curve>();
+        self.bytes.read_curve(offset)
+    }
+
+    /// Resolve the packed page-entry index for a codepoint via the two-level directory, or `None`
+    /// when its high part maps to an absent page.
+    fn entry_index(&self, cp: u32) -> Result<Option<usize>, ReadError> {
+        let hi = (cp >> 8) as usize;
+        if hi >= self.directory_count {
+            return Ok(None);
+        }
+        let page = self.bytes.read_u32(self.directory_offset + hi * size_of::<u32>())?;
+        if page == container::ABSENT_PAGE {
+            return Ok(None);
+        }
+        Ok(Some(page as usize * container::PAGE_SIZE + (cp & 0xFF) as usize))
+    }
+
+    fn raw_info(&self, index: usize) -> Result<GlyphInfo, ReadError> {
+        let offset = self.info_offset + index * size_of::<GlyphInfo>();
+        Ok(GlyphInfo::new(
+            self.bytes.read_u32(offset)?,
+            self.bytes.read_u32(offset + 4)?,
+            self.bytes.read_vec2(offset + 8)?,
+            self.bytes.read_vec2(offset + 16)?,
+        ))
+    }
+
+    /// Read a present (non-absent) info entry for a codepoint, or `None` for an absent page/entry.
+    fn present_info(&self, cp: u32) -> Result<Option<GlyphInfo>, ReadError> {
+        let Some(index) = self.entry_index(cp)? else {
+            return Ok(None);
+        };
+        let info = self.raw_info(index)?;
+        Ok((!info.is_absent()).then_some(info))
+    }
+
+    /// Look up the [`GlyphInfo`] for a codepoint, following the directory and substituting the
+    /// replacement glyph for a missing alphanumeric. An empty glyph has `start == end`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a resolved entry falls outside the file.
+    pub fn glyph_info(&self, cp: u32) -> Result<GlyphInfo, ReadError> {
+        if let Some(info) = self.present_info(cp)? {
+            return Ok(info);
+        }
+        if should_replace(cp) {
+            if let Some(info) = self.present_info(REPLACEMENT_CHARACTER as u32)? {
+                return Ok(info);
+            }
+        }
+        Ok(GlyphInfo::new(0, 0, glam::Vec2::ZERO, glam::Vec2::ZERO))
+    }
+
+    fn raw_metrics(&self, index: usize) -> Result<Metrics, ReadError> {
+        let base = self.metrics_offset + index * size_of::<Metrics>();
+        let mut fields = [0.0; Metrics::FIELDS];
+        for (i, field) in fields.iter_mut().enumerate() {
+            *field = self.bytes.read_f32(base + i * 4)?;
+        }
+        Ok(Metrics::new(fields))
+    }
+
+    /// Look up the [`Metrics`] for a codepoint, following the same directory and replacement path
+    /// as [`Self::glyph_info`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if a resolved entry falls outside the file.
+    pub fn metrics(&self, cp: u32) -> Result<Metrics, ReadError> {
+        if self.present_info(cp)?.is_some() {
+            return self.raw_metrics(self.entry_index(cp)?.unwrap());
+        }
+        if should_replace(cp) {
+            if let Some(index) = self.entry_index(REPLACEMENT_CHARACTER as u32)? {
+                return self.raw_metrics(index);
+            }
+        }
+        Ok(Metrics::default())
+    }
+
+    /// Read the `index`-th kerning pair.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pair falls outside the file.
+    pub fn kern_pair(&self, index: usize) -> Result<KernPair, ReadError> {
+        let offset = self.kerning_offset + index * size_of::<KernPair>();
+        Ok(KernPair {
+            left: u16::from_ne_bytes(self.bytes.read_array::<2>(offset)?),
+            right: u16::from_ne_bytes(self.bytes.read_array::<2>(offset + 2)?),
+            adjustment: self.bytes.read_f32(offset + 4)?,
+        })
+    }
+
+    /// Binary-search the kerning table for an ordered glyph pair, returning the adjustment.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a probed pair falls outside the file.
+    pub fn kerning(&self, left: u16, right: u16) -> Result<Option<f32>, ReadError> {
+        let (mut lo, mut hi) = (0, self.kerning_count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let pair = self.kern_pair(mid)?;
+            match (pair.left, pair.right).cmp(&(left, right)) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Ok(Some(pair.adjustment)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::{ABSENT_PAGE, DIRECTORY_LEN, PAGE_SIZE};
+
+    /// Build a small container in a temp file, read it back, and assert the whole format contract:
+    /// the hand-computed header offsets round-trip and every accessor returns the written values,
+    /// including the absent-entry replacement path.
+    #[test]
+    fn round_trip() {
+        let glyphs = [
+            Curve::zeroed()
+                .with_p0(glam::vec2(0.0, 0.0))
+                .with_p1(glam::vec2(0.5, 0.5))
+                .with_p2(glam::vec2(1.0, 1.0)),
+            Curve::zeroed()
+                .with_p0(glam::vec2(1.0, 1.0))
+                .with_p2(glam::vec2(0.0, 0.0))
+                .set_line_flag(),
+            Curve::zeroed().with_p2(glam::vec2(0.25, 0.75)),
+        ];
+
+        // Two pages: 'A' (0x41, high part 0) and the replacement char (0xFFFD, high part 0xFF).
+        let repl = PAGE_SIZE + (REPLACEMENT_CHARACTER as usize & 0xFF);
+        let mut directory = vec![ABSENT_PAGE; DIRECTORY_LEN];
+        directory[0] = 0;
+        directory[REPLACEMENT_CHARACTER as usize >> 8] = 1;
+
+        let mut info = vec![GlyphInfo::absent(); 2 * PAGE_SIZE];
+        let mut metrics = vec![Metrics::default(); 2 * PAGE_SIZE];
+        let a_info = GlyphInfo::new(0, 2, glam::vec2(0.1, 0.2), glam::vec2(0.5, 0.7));
+        let repl_info = GlyphInfo::new(2, 3, glam::vec2(0.0, 0.0), glam::vec2(0.4, 0.8));
+        info[0x41] = a_info;
+        info[repl] = repl_info;
+        let a_metrics = Metrics::new([0.6, 0.1, 0.2, 1.0, 0.0]);
+        let repl_metrics = Metrics::new([0.5, 0.0, 0.1, 1.0, 0.0]);
+        metrics[0x41] = a_metrics;
+        metrics[repl] = repl_metrics;
+
+        let kerning = [
+            KernPair { left: 1, right: 2, adjustment: -0.25 },
+            KernPair { left: 3, right: 4, adjustment: 0.5 },
+        ];
+
+        let path =
+            std::env::temp_dir().join(format!("bigtime-round-trip-{}.bin", std::process::id()));
+        container::write(&path, &glyphs, &directory, &info, &metrics, &kerning).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let container = Container::open(&bytes).unwrap();
+
+        // A bad magic is rejected before any payload is trusted.
+        assert_eq!(Container::open(b"nope").unwrap_err(), ReadError::BadMagic);
+
+        // Present glyph: info, outline curves and metrics all survive the round-trip.
+        assert_eq!(container.glyph_info('A' as u32).unwrap(), a_info);
+        assert!(!container.glyph_info('A' as u32).unwrap().is_absent());
+        assert_eq!(
+            container.glyph_info('A' as u32).unwrap().bbox_min(),
+            glam::vec2(0.1, 0.2)
+        );
+        assert_eq!(container.curve(0).unwrap(), glyphs[0]);
+        assert_eq!(container.curve(1).unwrap(), glyphs[1]);
+        assert_eq!(container.metrics('A' as u32).unwrap(), a_metrics);
+
+        // Absent alphanumeric falls back to the replacement glyph.
+        assert_eq!(container.glyph_info('B' as u32).unwrap(), repl_info);
+        assert_eq!(container.metrics('B' as u32).unwrap(), repl_metrics);
+
+        // Absent non-alphanumeric gets the empty sentinel, not the replacement.
+        let empty = container.glyph_info('@' as u32).unwrap();
+        assert_eq!((empty.start(), empty.end()), (0, 0));
+        assert_eq!(empty.bbox_size(), glam::Vec2::ZERO);
+
+        // Kerning is binary-searchable by glyph pair.
+        assert_eq!(container.kern_pair(0).unwrap(), kerning[0]);
+        assert_eq!(container.kerning(1, 2).unwrap(), Some(-0.25));
+        assert_eq!(container.kerning(3, 4).unwrap(), Some(0.5));
+        assert_eq!(container.kerning(9, 9).unwrap(), None);
+    }
+}